@@ -4,6 +4,7 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 
 use std::io::{Read, Write};
 use std::ops::DerefMut;
@@ -112,6 +113,16 @@ pub enum Event<T, D> {
     Messages(T, Vec<D>),
 }
 
+impl<T: Clone, D: Clone> Clone for Event<T, D> {
+    fn clone(&self) -> Self {
+        match *self {
+            Event::Start => Event::Start,
+            Event::Progress(ref vec) => Event::Progress(vec.clone()),
+            Event::Messages(ref time, ref data) => Event::Messages(time.clone(), data.clone()),
+        }
+    }
+}
+
 
 impl<T: Abomonation, D: Abomonation> Abomonation for Event<T,D> {
     #[inline] unsafe fn embalm(&mut self) {
@@ -146,6 +157,24 @@ pub struct EventLink<T, D> {
 
 impl<T, D> EventLink<T, D> { pub fn new() -> EventLink<T, D> { EventLink { event: Event::Start, next: RefCell::new(None) }}}
 
+/// A sequence of `Event`s driving the progress and data of a `ReplayOperator`.
+///
+/// `ReplayOperator` starts out holding one capability at `T::minimum()` per `EventIterator`,
+/// and treats the yielded `Event`s as edits against that baseline: `Event::Progress` deltas
+/// are applied to the held capability as they arrive, and `Event::Messages` are read off as
+/// data at the time they name. Implementations must uphold two invariants so replay sees a
+/// valid capability history:
+///
+/// 1. A `Progress` delta may only raise the count at a time `t` if the cumulative count at
+///    some time `t' <= t` is positive at that point in the stream (i.e. it must be covered
+///    by a capability already held, directly or by implication of an earlier, not-yet-spent
+///    count).
+/// 2. A `Messages(time, data)` event may only use a `time` for which the same cumulative-count
+///    condition holds, since emitting data requires a capability valid for that time.
+///
+/// In particular, the first `Progress` event observed is what releases `ReplayOperator`'s
+/// baseline capability at `T::minimum()`, by carrying a `-1` at `T::minimum()` alongside
+/// whatever counts describe where the captured computation actually starts.
 pub trait EventIterator<T, D> {
     fn next<'a>(&'a mut self) -> Option<&'a Event<T, D>>;
 }
@@ -180,7 +209,78 @@ impl<T, D> EventIterator<T, D> for Rc<EventLink<T, D>> {
 }
 
 
+// implementation for a shared queue using `std::sync::mpsc`.
+
+impl<T, D> EventPusher<T, D> for ::std::sync::mpsc::Sender<Event<T, D>> {
+    fn push(&mut self, event: Event<T, D>) {
+        // NOTE: An Err(..) here would indicate that the receiver has been torn down
+        //       (typically as a consequence of dropping the other end of the channel).
+        //       There is nothing to be done about it from here, so the error is ignored.
+        let _ = self.send(event);
+    }
+}
+
+/// A wrapper for `Receiver<Event<T, D>>` implementing `EventIterator<T, D>`.
+pub struct EventReceiver<T, D> {
+    receiver: ::std::sync::mpsc::Receiver<Event<T, D>>,
+    event: Option<Event<T, D>>,
+}
+
+impl<T, D> EventReceiver<T, D> {
+    /// Wraps a `Receiver<Event<T, D>>` as an `EventIterator<T, D>`.
+    pub fn new(receiver: ::std::sync::mpsc::Receiver<Event<T, D>>) -> Self {
+        EventReceiver {
+            receiver: receiver,
+            event: None,
+        }
+    }
+}
+
+impl<T, D> EventIterator<T, D> for EventReceiver<T, D> {
+    fn next<'a>(&'a mut self) -> Option<&'a Event<T, D>> {
+        self.event = self.receiver.try_recv().ok();
+        self.event.as_ref()
+    }
+}
+
+// Magic bytes and version for the length-prefixed frame format `EventWriter`/`EventReader`
+// speak. The magic/version pair is written once, at the start of the stream, so a reader can
+// reject a stream it doesn't know how to frame before it ever tries to `decode` anything; the
+// `u64` length prefix in front of each `Event` then lets a reader recognize a full frame
+// without speculatively decoding a partial buffer, even when that frame arrives split over
+// many reads. There is no resynchronization: the reader trusts every length prefix, so a
+// torn write (a partial frame followed by further frames) leaves it misaligned from there on.
+const FRAME_MAGIC: [u8; 4] = *b"TDCE";
+const FRAME_VERSION: u32 = 1;
+// the one-time 4-byte-magic + 4-byte-version stream header.
+const STREAM_HEADER_LEN: usize = 8;
+// the width of the `u64` length prefix in front of each frame's encoded `Event`.
+const FRAME_LEN_BYTES: usize = 8;
+
+fn write_u32_le<W: ::std::io::Write>(w: &mut W, val: u32) -> ::std::io::Result<()> {
+    let bytes = [val as u8, (val >> 8) as u8, (val >> 16) as u8, (val >> 24) as u8];
+    w.write_all(&bytes)
+}
+
+fn write_u64_le<W: ::std::io::Write>(w: &mut W, val: u64) -> ::std::io::Result<()> {
+    let bytes = [
+        val as u8, (val >> 8) as u8, (val >> 16) as u8, (val >> 24) as u8,
+        (val >> 32) as u8, (val >> 40) as u8, (val >> 48) as u8, (val >> 56) as u8,
+    ];
+    w.write_all(&bytes)
+}
+
+fn read_u32_le(bytes: &[u8]) -> u32 {
+    (bytes[0] as u32) | (bytes[1] as u32) << 8 | (bytes[2] as u32) << 16 | (bytes[3] as u32) << 24
+}
+
+fn read_u64_le(bytes: &[u8]) -> u64 {
+    (bytes[0] as u64) | (bytes[1] as u64) << 8 | (bytes[2] as u64) << 16 | (bytes[3] as u64) << 24 |
+    (bytes[4] as u64) << 32 | (bytes[5] as u64) << 40 | (bytes[6] as u64) << 48 | (bytes[7] as u64) << 56
+}
+
 pub struct EventWriter<T, D, W: ::std::io::Write> {
+    wrote_header: bool,
     buffer: Vec<u8>,
     stream: W,
     phant: ::std::marker::PhantomData<(T,D)>,
@@ -189,6 +289,7 @@ pub struct EventWriter<T, D, W: ::std::io::Write> {
 impl<T, D, W: ::std::io::Write> EventWriter<T, D, W> {
     pub fn new(w: W) -> EventWriter<T, D, W> {
         EventWriter {
+            wrote_header: false,
             buffer: vec![],
             stream: w,
             phant: ::std::marker::PhantomData,
@@ -198,7 +299,13 @@ impl<T, D, W: ::std::io::Write> EventWriter<T, D, W> {
 
 impl<T: Abomonation, D: Abomonation, W: ::std::io::Write> EventPusher<T, D> for EventWriter<T, D, W> {
     fn push(&mut self, event: Event<T, D>) {
+        if !self.wrote_header {
+            self.stream.write_all(&FRAME_MAGIC).unwrap();
+            write_u32_le(&mut self.stream, FRAME_VERSION).unwrap();
+            self.wrote_header = true;
+        }
         unsafe { ::abomonation::encode(&event, &mut self.buffer); }
+        write_u64_le(&mut self.stream, self.buffer.len() as u64).unwrap();
         self.stream.write_all(&self.buffer[..]).unwrap();
         self.buffer.clear();
     }
@@ -212,6 +319,8 @@ pub struct EventReader<T, D, R: ::std::io::Read> {
     buff2: Vec<u8>,
     consumed: usize,
     valid: usize,
+    header_validated: bool,
+    frame_len: Option<usize>,
     phant: ::std::marker::PhantomData<(T,D)>,
 }
 
@@ -224,37 +333,254 @@ impl<T, D, R: ::std::io::Read> EventReader<T, D, R> {
             buff2: vec![],
             consumed: 0,
             valid: 0,
+            header_validated: false,
+            frame_len: None,
             phant: ::std::marker::PhantomData,
         }
     }
+
+    // shifts any unconsumed bytes to the front of `buff1`, then reads more in behind them.
+    fn fill_buffer(&mut self) {
+        self.valid = shift_and_read(&mut self.reader, &mut self.bytes, &mut self.buff1, &mut self.buff2, &mut self.consumed);
+    }
+}
+
+// shifts the unconsumed tail of `buff1` to its front, reads more bytes from `reader` in behind
+// it via the `scratch` buffer, and returns the resulting valid length of `buff1`. Shared by
+// `EventReader` and `MuxEventReader`, which otherwise duplicate this buffer management exactly.
+fn shift_and_read<R: ::std::io::Read>(reader: &mut R, scratch: &mut Vec<u8>, buff1: &mut Vec<u8>, buff2: &mut Vec<u8>, consumed: &mut usize) -> usize {
+    if *consumed > 0 {
+        buff2.clear();
+        buff2.write_all(&mut buff1[*consumed..]).unwrap();
+        ::std::mem::swap(buff1, buff2);
+        *consumed = 0;
+    }
+
+    if let Ok(len) = reader.read(&mut scratch[..]) {
+        buff1.write_all(&scratch[..len]).unwrap();
+    }
+
+    buff1.len()
 }
 
 impl<T: Abomonation, D: Abomonation, R: ::std::io::Read> EventIterator<T, D> for EventReader<T, D, R> {
     fn next<'a>(&'a mut self) -> Option<&'a Event<T, D>> {
 
-        // if we can decode something, we should just return it! :D
-        if unsafe { ::abomonation::decode::<Event<T,D>>(&mut self.buff1[self.consumed..]) }.is_some() {
-            let (item, rest) = unsafe { ::abomonation::decode::<Event<T,D>>(&mut self.buff1[self.consumed..]) }.unwrap();
-            self.consumed = self.valid - rest.len();
-            return Some(item);
+        // before reading any frames, confirm the stream speaks the format we expect.
+        if !self.header_validated {
+            if self.valid - self.consumed >= STREAM_HEADER_LEN {
+                let magic = &self.buff1[self.consumed..self.consumed + 4];
+                assert_eq!(magic, &FRAME_MAGIC[..], "EventReader: stream does not start with the expected magic bytes");
+                let version = read_u32_le(&self.buff1[self.consumed + 4..self.consumed + STREAM_HEADER_LEN]);
+                assert_eq!(version, FRAME_VERSION, "EventReader: unsupported frame version {}", version);
+                self.consumed += STREAM_HEADER_LEN;
+                self.header_validated = true;
+            }
+            else {
+                self.fill_buffer();
+                return None;
+            }
+        }
+
+        // learn the length of the next frame, if we don't already know it.
+        if self.frame_len.is_none() {
+            if self.valid - self.consumed >= FRAME_LEN_BYTES {
+                self.frame_len = Some(read_u64_le(&self.buff1[self.consumed..self.consumed + FRAME_LEN_BYTES]) as usize);
+                self.consumed += FRAME_LEN_BYTES;
+            }
+            else {
+                self.fill_buffer();
+                return None;
+            }
+        }
+
+        // only decode once the whole frame is buffered.
+        let frame_len = self.frame_len.unwrap();
+        if self.valid - self.consumed >= frame_len {
+            let (item, _) = unsafe { ::abomonation::decode::<Event<T,D>>(&mut self.buff1[self.consumed..self.consumed + frame_len]) }.unwrap();
+            self.consumed += frame_len;
+            self.frame_len = None;
+            Some(item)
         }
         else {
-            // if we exhaust data we should shift back (if any shifting to do)
-            if self.consumed > 0 {
-                self.buff2.clear();
-                self.buff2.write_all(&mut self.buff1[self.consumed..]).unwrap();
-                ::std::mem::swap(&mut self.buff1, &mut self.buff2);
-                self.valid = self.buff1.len();
-                self.consumed = 0;
+            self.fill_buffer();
+            None
+        }
+    }
+}
+
+// the `u32` stream id plus `u64` payload length that prefixes each frame on a muxed stream.
+const MUX_FRAME_HEADER_LEN: usize = 4 + FRAME_LEN_BYTES;
+
+struct MuxWriterShared<W: ::std::io::Write> {
+    stream: W,
+    wrote_header: bool,
+    buffer: Vec<u8>,
+}
+
+/// Hands out per-stream `EventPusher` handles that multiplex onto a single `W`, so that many
+/// captured streams can flow down one connection. Each handle stamps every frame it writes
+/// with its own `u32` logical stream id, which a `MuxEventReader` on the other end uses to
+/// route decoded `Event`s back to the matching `replay_into`.
+pub struct MuxEventWriter<W: ::std::io::Write> {
+    shared: Rc<RefCell<MuxWriterShared<W>>>,
+}
+
+impl<W: ::std::io::Write> MuxEventWriter<W> {
+    pub fn new(w: W) -> MuxEventWriter<W> {
+        MuxEventWriter {
+            shared: Rc::new(RefCell::new(MuxWriterShared { stream: w, wrote_header: false, buffer: vec![] })),
+        }
+    }
+
+    /// Returns an `EventPusher` for logical stream `id`, sharing this writer's underlying `W`.
+    pub fn stream<T, D>(&self, id: u32) -> MuxEventPusher<T, D, W> {
+        MuxEventPusher { id: id, shared: self.shared.clone(), phant: ::std::marker::PhantomData }
+    }
+}
+
+/// An `EventPusher` for one logical stream of a `MuxEventWriter`.
+pub struct MuxEventPusher<T, D, W: ::std::io::Write> {
+    id: u32,
+    shared: Rc<RefCell<MuxWriterShared<W>>>,
+    phant: ::std::marker::PhantomData<(T,D)>,
+}
+
+impl<T: Abomonation, D: Abomonation, W: ::std::io::Write> EventPusher<T, D> for MuxEventPusher<T, D, W> {
+    fn push(&mut self, event: Event<T, D>) {
+        let shared = &mut *self.shared.borrow_mut();
+        if !shared.wrote_header {
+            shared.stream.write_all(&FRAME_MAGIC).unwrap();
+            write_u32_le(&mut shared.stream, FRAME_VERSION).unwrap();
+            shared.wrote_header = true;
+        }
+        unsafe { ::abomonation::encode(&event, &mut shared.buffer); }
+        write_u32_le(&mut shared.stream, self.id).unwrap();
+        write_u64_le(&mut shared.stream, shared.buffer.len() as u64).unwrap();
+        shared.stream.write_all(&shared.buffer[..]).unwrap();
+        shared.buffer.clear();
+    }
+}
+
+struct MuxReaderShared<T, D, R: ::std::io::Read> {
+    reader: R,
+    bytes: Vec<u8>,
+    buff1: Vec<u8>,
+    buff2: Vec<u8>,
+    consumed: usize,
+    valid: usize,
+    header_validated: bool,
+    frame: Option<(u32, usize)>,
+    queues: HashMap<u32, VecDeque<Event<T, D>>>,
+}
+
+impl<T: Abomonation+Clone, D: Abomonation+Clone, R: ::std::io::Read> MuxReaderShared<T, D, R> {
+
+    fn fill_buffer(&mut self) {
+        self.valid = shift_and_read(&mut self.reader, &mut self.bytes, &mut self.buff1, &mut self.buff2, &mut self.consumed);
+    }
+
+    // Decodes every complete frame currently sitting in the buffer, routing each `Event` onto
+    // its stream id's queue in the order the frames arrived on the wire. Ids are never
+    // prioritized here: every complete frame is decoded in wire order, whatever its id, so one
+    // id with a lot of frames buffered cannot keep another id's already-arrived frames from
+    // being decoded and queued up for its reader.
+    fn drain_buffered_frames(&mut self) {
+        loop {
+            if !self.header_validated {
+                if self.valid - self.consumed >= STREAM_HEADER_LEN {
+                    let magic = &self.buff1[self.consumed..self.consumed + 4];
+                    assert_eq!(magic, &FRAME_MAGIC[..], "MuxEventReader: stream does not start with the expected magic bytes");
+                    let version = read_u32_le(&self.buff1[self.consumed + 4..self.consumed + STREAM_HEADER_LEN]);
+                    assert_eq!(version, FRAME_VERSION, "MuxEventReader: unsupported frame version {}", version);
+                    self.consumed += STREAM_HEADER_LEN;
+                    self.header_validated = true;
+                }
+                else {
+                    return;
+                }
             }
 
-            if let Ok(len) = self.reader.read(&mut self.bytes[..]) {
-                self.buff1.write_all(&self.bytes[..len]).unwrap();
-                self.valid = self.buff1.len();
+            if self.frame.is_none() {
+                if self.valid - self.consumed >= MUX_FRAME_HEADER_LEN {
+                    let id = read_u32_le(&self.buff1[self.consumed..self.consumed + 4]);
+                    let len = read_u64_le(&self.buff1[self.consumed + 4..self.consumed + MUX_FRAME_HEADER_LEN]) as usize;
+                    self.frame = Some((id, len));
+                    self.consumed += MUX_FRAME_HEADER_LEN;
+                }
+                else {
+                    return;
+                }
+            }
+
+            let (id, len) = self.frame.unwrap();
+            if self.valid - self.consumed >= len {
+                let event = {
+                    let (item, _) = unsafe { ::abomonation::decode::<Event<T,D>>(&mut self.buff1[self.consumed..self.consumed + len]) }.unwrap();
+                    item.clone()
+                };
+                self.consumed += len;
+                self.frame = None;
+                self.queues.entry(id).or_insert_with(VecDeque::new).push_back(event);
+            }
+            else {
+                return;
             }
+        }
+    }
+
+    // drains whatever is already buffered, then tops up with (at most) one more read.
+    fn pump(&mut self) {
+        self.drain_buffered_frames();
+        self.fill_buffer();
+        self.drain_buffered_frames();
+    }
+}
+
+/// Demultiplexes the frames a `MuxEventWriter` wrote to a single `R`, handing out one
+/// `EventIterator` per logical stream id.
+pub struct MuxEventReader<T, D, R: ::std::io::Read> {
+    shared: Rc<RefCell<MuxReaderShared<T, D, R>>>,
+}
+
+impl<T, D, R: ::std::io::Read> MuxEventReader<T, D, R> {
+    pub fn new(r: R) -> MuxEventReader<T, D, R> {
+        MuxEventReader {
+            shared: Rc::new(RefCell::new(MuxReaderShared {
+                reader: r,
+                bytes: vec![0u8; 1 << 20],
+                buff1: vec![],
+                buff2: vec![],
+                consumed: 0,
+                valid: 0,
+                header_validated: false,
+                frame: None,
+                queues: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Returns an `EventIterator` yielding only the events written for logical stream `id`.
+    pub fn stream(&self, id: u32) -> MuxEventIterator<T, D, R> {
+        MuxEventIterator { id: id, shared: self.shared.clone(), event: None }
+    }
+}
+
+/// An `EventIterator` for one logical stream of a `MuxEventReader`.
+pub struct MuxEventIterator<T, D, R: ::std::io::Read> {
+    id: u32,
+    shared: Rc<RefCell<MuxReaderShared<T, D, R>>>,
+    event: Option<Event<T, D>>,
+}
 
-            return None;
+impl<T: Abomonation+Clone, D: Abomonation+Clone, R: ::std::io::Read> EventIterator<T, D> for MuxEventIterator<T, D, R> {
+    fn next<'a>(&'a mut self) -> Option<&'a Event<T, D>> {
+        let mut shared = self.shared.borrow_mut();
+        if shared.queues.get(&self.id).map_or(true, |q| q.is_empty()) {
+            shared.pump();
         }
+        self.event = shared.queues.get_mut(&self.id).and_then(|q| q.pop_front());
+        self.event.as_ref()
     }
 }
 
@@ -263,6 +589,23 @@ pub trait Replay<T: Timestamp, D: Data> {
 }
 
 impl<T: Timestamp, D: Data, I: EventIterator<T, D>+'static> Replay<T, D> for I {
+    fn replay_into<S: Scope<Timestamp=T>>(self, scope: &mut S) -> Stream<S, D>{
+        vec![self].replay_into(scope)
+    }
+}
+
+// Replays from several `EventIterator`s at once, e.g. one per captured worker, summing the
+// capability counts of all of them per timestamp, so that a time stays held while any one
+// stream still holds it and the combined output frontier is the meet of the individual
+// streams' frontiers.
+//
+// This is `Vec<I>` rather than a fully generic `V: IntoIterator<Item=I>` blanket: the latter
+// would conflict (E0119) with the `impl<I: EventIterator> Replay for I` above, since the
+// compiler cannot prove the two are disjoint (nothing rules out a single type someday
+// implementing both `EventIterator` and `IntoIterator<Item=EventIterator>`). Collect into a
+// `Vec` at the call site (`my_iterable.into_iter().collect::<Vec<_>>().replay_into(scope)`)
+// if your sources aren't already one.
+impl<T: Timestamp, D: Data, I: EventIterator<T, D>+'static> Replay<T, D> for Vec<I> {
     fn replay_into<S: Scope<Timestamp=T>>(self, scope: &mut S) -> Stream<S, D>{
        let (targets, registrar) = Tee::<S::Timestamp, D>::new();
        let operator = ReplayOperator {
@@ -285,9 +628,12 @@ impl<T:Timestamp, D: Data, P: EventPusher<T, D>> Operate<T> for CaptureOperator<
     fn inputs(&self) -> usize { 1 }
     fn outputs(&self) -> usize { 0 }
 
-    // we need to set the initial value of the frontier
+    // we need to set the initial value of the frontier, cancelling the baseline capability
+    // that `ReplayOperator` assumes at `T::minimum()` before it has read anything.
     fn set_external_summary(&mut self, _: Vec<Vec<Antichain<T::Summary>>>, counts: &mut [CountMap<T>]) {
-        self.events.push(Event::Progress(counts[0].clone().into_inner()));
+        let mut initial = counts[0].clone();
+        initial.update(&T::minimum(), -1);
+        self.events.push(Event::Progress(initial.into_inner()));
         counts[0].clear();
     }
 
@@ -307,7 +653,7 @@ impl<T:Timestamp, D: Data, P: EventPusher<T, D>> Operate<T> for CaptureOperator<
 }
 
 struct ReplayOperator<T:Timestamp, D: Data, I: EventIterator<T, D>> {
-    events: I,
+    events: Vec<I>,
     output: PushBuffer<T, D, PushCounter<T, D, Tee<T, D>>>,
 }
 
@@ -318,51 +664,35 @@ impl<T:Timestamp, D: Data, I: EventIterator<T, D>> Operate<T> for ReplayOperator
 
     fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Vec<CountMap<T>>) {
 
-        // panics if the event link has not been initialized; should only happen if no one has
-        // called set_external_summary on the `CaptureOperator`. So, please don't use this in the
-        // same graph as the `CaptureOperator`.
-
-        // TODO : use Default::default() as the initial time, and in the first Event we dequeue,
-        // TODO : announce that we've moved beyond Default::default().
-
-        loop {
-            let event = self.events.next();
-            if let Some(event) = event {
-                let mut result = CountMap::new();
-                if let &Event::Progress(ref vec) = event {
-                    for &(ref time, delta) in vec {
-                        result.update(time, delta);
-                    }
-                }
-                return (vec![], vec![result]);
-            }
+        // Each `EventIterator` starts out holding one capability at `T::minimum()`. This makes
+        // replay self-contained: it does not need to block reading the stream to learn the
+        // real starting frontier, which in turn means capture and replay can coexist in the
+        // same computation. The capture side is responsible for retiring this baseline with a
+        // `-1` at `T::minimum()` in its first `Event::Progress`, once it knows where the
+        // captured computation actually starts.
+        let mut initial = CountMap::new();
+        for _ in self.events.iter() {
+            initial.update(&T::minimum(), 1);
         }
-        // if let Some(event) = self.events.next() {
-        //     if let &Event::Progress(ref vec) = event {
-        //         for &(ref time, delta) in vec {
-        //             result.update(time, delta);
-        //         }
-        //     }
-        // }
-        // else {
-        //     panic!("uninitialized replay; possibly in same computation as capture?");
-        // }
+        (vec![], vec![initial])
     }
 
     fn pull_internal_progress(&mut self, _: &mut [CountMap<T>], internal: &mut [CountMap<T>], produced: &mut [CountMap<T>]) -> bool {
 
-        while let Some(event) = self.events.next() {
-            match *event {
-                Event::Start => { },
-                Event::Progress(ref vec) => {
-                    for &(ref time, delta) in vec {
-                        internal[0].update(time, delta);
-                    }
-                },
-                Event::Messages(ref time, ref data) => {
-                    let mut session = self.output.session(time);
-                    for datum in data {
-                        session.give(datum.clone());
+        for events in self.events.iter_mut() {
+            while let Some(event) = events.next() {
+                match *event {
+                    Event::Start => { },
+                    Event::Progress(ref vec) => {
+                        for &(ref time, delta) in vec {
+                            internal[0].update(time, delta);
+                        }
+                    },
+                    Event::Messages(ref time, ref data) => {
+                        let mut session = self.output.session(time);
+                        for datum in data {
+                            session.give(datum.clone());
+                        }
                     }
                 }
             }
@@ -382,7 +712,7 @@ mod tests {
     use ::Configuration;
     use dataflow::*;
     use dataflow::operators::{Capture, ToStream, Inspect};
-    use super::{EventLink, Replay, EventWriter, EventReader};
+    use super::{Event, EventIterator, EventPusher, EventLink, Replay, EventWriter, EventReader, EventReceiver, MuxEventWriter, MuxEventReader};
     use std::rc::Rc;
 
     use std::net::{TcpListener, TcpStream};
@@ -431,4 +761,146 @@ mod tests {
         });
     }
 
+
+    #[test]
+    fn mpsc_channel() {
+
+        // initializes and runs a timely dataflow computation
+        ::execute(Configuration::Thread, |computation| {
+
+            let (send, recv) = ::std::sync::mpsc::channel();
+
+            computation.scoped::<u64,_,_>(|scope1|
+                (0..10u64)
+                    .to_stream(scope1)
+                    .capture_into(send)
+            );
+
+            computation.scoped::<u64,_,_>(|scope2| {
+                EventReceiver::new(recv)
+                    .replay_into(scope2)
+                    .inspect(|x| println!("replayed: {:?}", x));
+            })
+        });
+    }
+
+
+    #[test]
+    fn multiple_sources() {
+
+        // initializes and runs a timely dataflow computation
+        ::execute(Configuration::Thread, |computation| {
+            let handle1a = Rc::new(EventLink::new());
+            let handle1b = handle1a.clone();
+            let handle2a = Rc::new(EventLink::new());
+            let handle2b = handle2a.clone();
+
+            computation.scoped::<u64,_,_>(|builder| {
+                (0..10).to_stream(builder).capture_into(handle1a);
+                (10..20).to_stream(builder).capture_into(handle2a);
+            });
+
+            computation.scoped(|builder| {
+                vec![handle1b, handle2b]
+                    .replay_into(builder)
+                    .inspect(|x| println!("replayed: {:?}", x));
+            })
+        });
+    }
+
+
+    #[test]
+    fn replay_in_same_scope_as_capture() {
+
+        // capture and replay used to be unable to coexist in the same graph, because
+        // `get_internal_summary` blocked waiting for the first `Progress` event from a
+        // `CaptureOperator` that the scheduler hadn't gotten around to running yet.
+        ::execute(Configuration::Thread, |computation| {
+            let handle1 = Rc::new(EventLink::new());
+            let handle2 = handle1.clone();
+
+            computation.scoped::<u64,_,_>(|builder| {
+                (0..10).to_stream(builder)
+                       .capture_into(handle1);
+
+                handle2.replay_into(builder)
+                       .inspect(|x| println!("replayed: {:?}", x));
+            });
+        });
+    }
+
+
+    // a `Read` that only ever hands back a single byte per call, to exercise `EventReader`'s
+    // handling of a frame that arrives split across many `read` calls.
+    struct OneByteAtATime<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> ::std::io::Read for OneByteAtATime<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+            if buf.is_empty() || self.pos >= self.bytes.len() { return Ok(0); }
+            buf[0] = self.bytes[self.pos];
+            self.pos += 1;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn torn_reads() {
+
+        let mut buffer = Vec::new();
+        {
+            let mut writer = EventWriter::new(&mut buffer);
+            writer.push(Event::Messages(1u64, vec![1, 2, 3]));
+            writer.push(Event::Messages(2u64, vec![4, 5]));
+        }
+
+        let mut reader = EventReader::<u64, i32, _>::new(OneByteAtATime { bytes: &buffer[..], pos: 0 });
+
+        let mut seen = Vec::new();
+        while seen.len() < 2 {
+            if let Some(&Event::Messages(ref time, ref data)) = reader.next() {
+                seen.push((*time, data.clone()));
+            }
+        }
+
+        assert_eq!(seen, vec![(1u64, vec![1, 2, 3]), (2u64, vec![4, 5])]);
+    }
+
+
+    #[test]
+    fn mux_streams() {
+
+        let mut buffer = Vec::new();
+        {
+            let writer = MuxEventWriter::new(&mut buffer);
+            let mut a = writer.stream::<u64, i32>(0);
+            let mut b = writer.stream::<u64, i32>(1);
+            a.push(Event::Messages(1u64, vec![1]));
+            b.push(Event::Messages(2u64, vec![2]));
+            a.push(Event::Messages(3u64, vec![3]));
+        }
+
+        let reader = MuxEventReader::<u64, i32, _>::new(&buffer[..]);
+        let mut a = reader.stream(0);
+        let mut b = reader.stream(1);
+
+        let mut seen_a = Vec::new();
+        let mut seen_b = Vec::new();
+        while seen_a.len() < 2 || seen_b.len() < 1 {
+            if let Some(&Event::Messages(ref time, ref data)) = a.next() {
+                seen_a.push((*time, data.clone()));
+            }
+            if let Some(&Event::Messages(ref time, ref data)) = b.next() {
+                seen_b.push((*time, data.clone()));
+            }
+        }
+
+        // each iterator only ever sees the frames written for its own id, in the order
+        // they were written, regardless of how the two ids were interleaved on the wire.
+        assert_eq!(seen_a, vec![(1u64, vec![1]), (3u64, vec![3])]);
+        assert_eq!(seen_b, vec![(2u64, vec![2])]);
+    }
+
 }